@@ -0,0 +1,355 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Built-in load generator for the gas pool.
+//!
+//! [`BenchRunner`] drives configurable concurrent `reserve_gas` -> `execute_tx`
+//! cycles against a running pool and records the full round-trip latency
+//! distribution into its own [`BenchMetrics`] histogram set. The histograms are
+//! registered into a [`prometheus::Registry`] and reuse the same
+//! [`mysten_metrics::histogram::Histogram`] type as production
+//! [`crate::metrics::GasPoolMetrics`], so bench results scrape identically and
+//! exercise the full pool + signer path (including the `SidecarTxSigner`
+//! network hop) without wiring up an external tool.
+
+use mysten_metrics::histogram::Histogram;
+use prometheus::Registry;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::warn;
+
+/// A gas reservation handed back by [`GasPoolClient::reserve_gas`].
+#[derive(Debug, Clone)]
+pub struct Reservation {
+    pub reservation_id: u64,
+    pub gas_coin_count: usize,
+}
+
+/// The subset of the gas pool RPC surface the bench runner exercises. A real
+/// deployment backs this with an HTTP client against the running pool; tests
+/// back it with an in-process stub.
+#[async_trait::async_trait]
+pub trait GasPoolClient: Send + Sync {
+    async fn reserve_gas(
+        &self,
+        target_gas_budget: u64,
+        reserve_duration_secs: u64,
+    ) -> anyhow::Result<Reservation>;
+
+    async fn execute_tx(&self, reservation: Reservation) -> anyhow::Result<()>;
+}
+
+/// Ramp profile describing how offered load changes over the run.
+#[derive(Debug, Clone)]
+pub enum RampProfile {
+    /// Constant offered load.
+    Fixed { rps: u64 },
+    /// Start at `start_rps` and add `step_rps` every `step_interval`.
+    Step {
+        start_rps: u64,
+        step_rps: u64,
+        step_interval: Duration,
+    },
+    /// Steady `base_rps` punctuated by `burst_rps` spikes of `burst_duration`
+    /// that recur every `burst_interval`.
+    Burst {
+        base_rps: u64,
+        burst_rps: u64,
+        burst_interval: Duration,
+        burst_duration: Duration,
+    },
+}
+
+impl RampProfile {
+    /// The target RPS at `elapsed` into the run.
+    fn rps_at(&self, elapsed: Duration) -> u64 {
+        match self {
+            RampProfile::Fixed { rps } => *rps,
+            RampProfile::Step {
+                start_rps,
+                step_rps,
+                step_interval,
+            } => {
+                let steps = elapsed.as_nanos() / step_interval.as_nanos().max(1);
+                start_rps + step_rps * steps as u64
+            }
+            RampProfile::Burst {
+                base_rps,
+                burst_rps,
+                burst_interval,
+                burst_duration,
+            } => {
+                let phase = Duration::from_nanos(
+                    (elapsed.as_nanos() % burst_interval.as_nanos().max(1)) as u64,
+                );
+                if phase < *burst_duration {
+                    *burst_rps
+                } else {
+                    *base_rps
+                }
+            }
+        }
+    }
+}
+
+/// Parameters for a bench run.
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    pub profile: RampProfile,
+    pub duration: Duration,
+    /// Maximum in-flight reserve -> execute cycles.
+    pub max_concurrency: usize,
+    pub target_gas_budget: u64,
+    pub reserve_duration_secs: u64,
+}
+
+/// Histogram set for a bench run, mirroring the naming and registration style
+/// of [`crate::metrics::GasPoolMetrics`].
+pub struct BenchMetrics {
+    pub reserve_latency_ms: Histogram,
+    pub execute_latency_ms: Histogram,
+    pub end_to_end_latency_ms: Histogram,
+    pub coins_per_reservation: Histogram,
+}
+
+impl BenchMetrics {
+    pub fn new(registry: &Registry) -> Arc<Self> {
+        Arc::new(Self {
+            reserve_latency_ms: Histogram::new_in_registry(
+                "bench_reserve_latency_ms",
+                "reserve_gas round-trip latency in milliseconds",
+                registry,
+            ),
+            execute_latency_ms: Histogram::new_in_registry(
+                "bench_execute_latency_ms",
+                "execute_tx round-trip latency in milliseconds",
+                registry,
+            ),
+            end_to_end_latency_ms: Histogram::new_in_registry(
+                "bench_end_to_end_latency_ms",
+                "End-to-end reserve-to-release latency in milliseconds",
+                registry,
+            ),
+            coins_per_reservation: Histogram::new_in_registry(
+                "bench_coins_per_reservation",
+                "Number of gas coins returned per reservation",
+                registry,
+            ),
+        })
+    }
+
+    pub fn new_for_testing() -> Arc<Self> {
+        Self::new(&Registry::new())
+    }
+}
+
+/// Drives load against a [`GasPoolClient`] and records it into [`BenchMetrics`].
+pub struct BenchRunner {
+    client: Arc<dyn GasPoolClient>,
+    metrics: Arc<BenchMetrics>,
+    config: BenchConfig,
+}
+
+impl BenchRunner {
+    pub fn new(
+        client: Arc<dyn GasPoolClient>,
+        metrics: Arc<BenchMetrics>,
+        config: BenchConfig,
+    ) -> Self {
+        Self {
+            client,
+            metrics,
+            config,
+        }
+    }
+
+    /// Run the configured ramp to completion. Returns the number of cycles
+    /// successfully completed. Offered load is re-evaluated once per second
+    /// against the ramp profile; a semaphore bounds in-flight cycles so that a
+    /// saturated pool back-pressures the generator instead of unbounded growth.
+    pub async fn run(&self) -> u64 {
+        let permits = Arc::new(Semaphore::new(self.config.max_concurrency));
+        let mut cycles = JoinSet::new();
+        let mut completed = 0;
+        let started = Instant::now();
+
+        let mut ticker = interval(Duration::from_secs(1));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        while started.elapsed() < self.config.duration {
+            ticker.tick().await;
+            // Drain whatever finished during the last tick so the JoinSet does
+            // not grow unbounded over a long or high-RPS run.
+            while let Some(result) = cycles.try_join_next() {
+                if matches!(result, Ok(true)) {
+                    completed += 1;
+                }
+            }
+            let target_rps = self.config.profile.rps_at(started.elapsed());
+            for _ in 0..target_rps {
+                let Ok(permit) = permits.clone().try_acquire_owned() else {
+                    // Generator is saturated for this tick; skip remaining load.
+                    break;
+                };
+                let client = self.client.clone();
+                let metrics = self.metrics.clone();
+                let cfg = self.config.clone();
+                cycles.spawn(async move {
+                    let _permit = permit;
+                    run_one_cycle(client, metrics, cfg).await
+                });
+            }
+        }
+
+        // Wait out the still-in-flight cycles.
+        while let Some(result) = cycles.join_next().await {
+            if matches!(result, Ok(true)) {
+                completed += 1;
+            }
+        }
+        completed
+    }
+}
+
+/// Execute a single reserve -> execute cycle, recording each leg's latency.
+/// Returns whether the cycle completed successfully.
+async fn run_one_cycle(
+    client: Arc<dyn GasPoolClient>,
+    metrics: Arc<BenchMetrics>,
+    cfg: BenchConfig,
+) -> bool {
+    let cycle_start = Instant::now();
+
+    let reserve_start = Instant::now();
+    let reservation = match client
+        .reserve_gas(cfg.target_gas_budget, cfg.reserve_duration_secs)
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            warn!("bench reserve_gas failed: {e:?}");
+            return false;
+        }
+    };
+    metrics
+        .reserve_latency_ms
+        .report(reserve_start.elapsed().as_millis() as u64);
+    metrics
+        .coins_per_reservation
+        .report(reservation.gas_coin_count as u64);
+
+    let execute_start = Instant::now();
+    if let Err(e) = client.execute_tx(reservation).await {
+        warn!("bench execute_tx failed: {e:?}");
+        return false;
+    }
+    metrics
+        .execute_latency_ms
+        .report(execute_start.elapsed().as_millis() as u64);
+    metrics
+        .end_to_end_latency_ms
+        .report(cycle_start.elapsed().as_millis() as u64);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StubClient {
+        fail_reserve: bool,
+        fail_execute: bool,
+        coin_count: usize,
+    }
+
+    #[async_trait::async_trait]
+    impl GasPoolClient for StubClient {
+        async fn reserve_gas(&self, _budget: u64, _duration: u64) -> anyhow::Result<Reservation> {
+            if self.fail_reserve {
+                anyhow::bail!("reserve failed");
+            }
+            Ok(Reservation {
+                reservation_id: 1,
+                gas_coin_count: self.coin_count,
+            })
+        }
+
+        async fn execute_tx(&self, _reservation: Reservation) -> anyhow::Result<()> {
+            if self.fail_execute {
+                anyhow::bail!("execute failed");
+            }
+            Ok(())
+        }
+    }
+
+    fn cycle_config() -> BenchConfig {
+        BenchConfig {
+            profile: RampProfile::Fixed { rps: 0 },
+            duration: Duration::ZERO,
+            max_concurrency: 1,
+            target_gas_budget: 1_000,
+            reserve_duration_secs: 10,
+        }
+    }
+
+    #[test]
+    fn step_profile_ramps_per_interval() {
+        let profile = RampProfile::Step {
+            start_rps: 10,
+            step_rps: 5,
+            step_interval: Duration::from_secs(1),
+        };
+        assert_eq!(profile.rps_at(Duration::ZERO), 10);
+        assert_eq!(profile.rps_at(Duration::from_millis(1500)), 15);
+        assert_eq!(profile.rps_at(Duration::from_millis(2500)), 20);
+    }
+
+    #[test]
+    fn burst_profile_spikes_within_window() {
+        let profile = RampProfile::Burst {
+            base_rps: 5,
+            burst_rps: 50,
+            burst_interval: Duration::from_secs(10),
+            burst_duration: Duration::from_secs(2),
+        };
+        // Inside the burst window at the start of each interval.
+        assert_eq!(profile.rps_at(Duration::ZERO), 50);
+        assert_eq!(profile.rps_at(Duration::from_secs(11)), 50);
+        // Outside the burst window falls back to the base rate.
+        assert_eq!(profile.rps_at(Duration::from_secs(3)), 5);
+    }
+
+    #[tokio::test]
+    async fn cycle_counts_success() {
+        let client = Arc::new(StubClient {
+            fail_reserve: false,
+            fail_execute: false,
+            coin_count: 3,
+        });
+        let ok = run_one_cycle(client, BenchMetrics::new_for_testing(), cycle_config()).await;
+        assert!(ok);
+    }
+
+    #[tokio::test]
+    async fn cycle_counts_reserve_and_execute_failures() {
+        let reserve_fail = Arc::new(StubClient {
+            fail_reserve: true,
+            fail_execute: false,
+            coin_count: 1,
+        });
+        assert!(
+            !run_one_cycle(reserve_fail, BenchMetrics::new_for_testing(), cycle_config()).await
+        );
+
+        let execute_fail = Arc::new(StubClient {
+            fail_reserve: false,
+            fail_execute: true,
+            coin_count: 1,
+        });
+        assert!(
+            !run_one_cycle(execute_fail, BenchMetrics::new_for_testing(), cycle_config()).await
+        );
+    }
+}
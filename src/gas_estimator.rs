@@ -0,0 +1,179 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dry-run based gas budget auto-estimation for the reserve path.
+//!
+//! Clients normally supply `target_gas_budget` to `reserve_gas` (surfaced as
+//! the `target_gas_budget_per_request` histogram). This module offers an
+//! optional mode in which the caller instead hands the pool the transaction
+//! kind up front: the pool dry-runs it against a fullnode, applies a
+//! configurable safety multiplier to the reported cost, and reserves coins to
+//! cover the resulting estimate. This removes the common failure mode of
+//! under-budgeted reservations that then fail in `execute_tx`.
+
+use crate::metrics::GasPoolMetrics;
+use std::sync::Arc;
+use sui_types::base_types::SuiAddress;
+use sui_types::transaction::TransactionKind;
+
+/// Fullnode dry-run surface used to estimate the gas cost of a transaction.
+/// A real deployment backs this with a `SuiClient`; it is a trait so the
+/// estimator can be exercised without a live fullnode.
+#[async_trait::async_trait]
+pub trait DryRunClient: Send + Sync {
+    /// Dry-run `tx_kind` as `sender` and return the net gas cost, i.e.
+    /// computation + storage cost minus storage rebate, in MIST.
+    async fn dry_run_gas_cost(
+        &self,
+        tx_kind: &TransactionKind,
+        sender: SuiAddress,
+    ) -> anyhow::Result<u64>;
+}
+
+/// Configuration for dry-run auto-estimation.
+#[derive(Debug, Clone)]
+pub struct EstimationConfig {
+    /// Safety multiplier applied to the dry-run cost, in basis points
+    /// (e.g. 12000 = 1.2x). Covers fullnode/reservation drift.
+    pub safety_multiplier_bps: u64,
+    /// Reservations whose estimated budget exceeds this ceiling are rejected.
+    pub max_gas_budget: u64,
+}
+
+impl Default for EstimationConfig {
+    fn default() -> Self {
+        Self {
+            safety_multiplier_bps: 12_000,
+            max_gas_budget: 50_000_000_000,
+        }
+    }
+}
+
+/// Why auto-estimation declined to produce a usable budget.
+#[derive(Debug, thiserror::Error)]
+pub enum EstimationError {
+    #[error("dry-run failed: {0}")]
+    DryRun(#[from] anyhow::Error),
+    #[error("estimated gas budget {estimated} exceeds ceiling {ceiling}")]
+    OverCeiling { estimated: u64, ceiling: u64 },
+}
+
+/// Computes reservation budgets from fullnode dry-runs.
+pub struct GasBudgetEstimator {
+    dry_run_client: Arc<dyn DryRunClient>,
+    config: EstimationConfig,
+    metrics: Arc<GasPoolMetrics>,
+}
+
+impl GasBudgetEstimator {
+    pub fn new(
+        dry_run_client: Arc<dyn DryRunClient>,
+        config: EstimationConfig,
+        metrics: Arc<GasPoolMetrics>,
+    ) -> Self {
+        Self {
+            dry_run_client,
+            config,
+            metrics,
+        }
+    }
+
+    /// Estimate the gas budget to reserve for `tx_kind` sponsored by `sender`.
+    /// The dry-run cost is scaled by the safety multiplier and checked against
+    /// the configured ceiling; rejected estimates bump
+    /// `num_reservations_rejected_over_ceiling`.
+    pub async fn estimate_budget(
+        &self,
+        tx_kind: &TransactionKind,
+        sender: SuiAddress,
+    ) -> Result<u64, EstimationError> {
+        let cost = self.dry_run_client.dry_run_gas_cost(tx_kind, sender).await?;
+        let estimated = cost.saturating_mul(self.config.safety_multiplier_bps) / 10_000;
+        if estimated > self.config.max_gas_budget {
+            self.metrics.num_reservations_rejected_over_ceiling.inc();
+            return Err(EstimationError::OverCeiling {
+                estimated,
+                ceiling: self.config.max_gas_budget,
+            });
+        }
+        self.metrics.estimated_gas_budget_per_request.report(estimated);
+        Ok(estimated)
+    }
+
+    /// Record the actual gas used once a sponsored transaction executes, so the
+    /// gap against the estimate can be observed and the safety multiplier
+    /// tuned.
+    pub fn record_actual_gas_used(&self, actual_gas_used: u64) {
+        self.metrics.actual_gas_used_per_request.report(actual_gas_used);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+    struct StubDryRun {
+        cost: u64,
+    }
+
+    #[async_trait::async_trait]
+    impl DryRunClient for StubDryRun {
+        async fn dry_run_gas_cost(
+            &self,
+            _tx_kind: &TransactionKind,
+            _sender: SuiAddress,
+        ) -> anyhow::Result<u64> {
+            Ok(self.cost)
+        }
+    }
+
+    fn empty_tx_kind() -> TransactionKind {
+        TransactionKind::ProgrammableTransaction(ProgrammableTransactionBuilder::new().finish())
+    }
+
+    fn estimator(cost: u64, config: EstimationConfig) -> (GasBudgetEstimator, Arc<GasPoolMetrics>) {
+        let metrics = GasPoolMetrics::new_for_testing();
+        let estimator = GasBudgetEstimator::new(
+            Arc::new(StubDryRun { cost }),
+            config,
+            metrics.clone(),
+        );
+        (estimator, metrics)
+    }
+
+    #[tokio::test]
+    async fn applies_safety_multiplier() {
+        let config = EstimationConfig {
+            safety_multiplier_bps: 12_000,
+            max_gas_budget: u64::MAX,
+        };
+        let (estimator, _metrics) = estimator(1_000, config);
+        let budget = estimator
+            .estimate_budget(&empty_tx_kind(), SuiAddress::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(budget, 1_200);
+    }
+
+    #[tokio::test]
+    async fn rejects_over_ceiling_and_bumps_counter() {
+        let config = EstimationConfig {
+            safety_multiplier_bps: 10_000,
+            max_gas_budget: 500,
+        };
+        let (estimator, metrics) = estimator(1_000, config);
+        let err = estimator
+            .estimate_budget(&empty_tx_kind(), SuiAddress::ZERO)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            EstimationError::OverCeiling {
+                estimated: 1_000,
+                ceiling: 500
+            }
+        ));
+        assert_eq!(metrics.num_reservations_rejected_over_ceiling.get(), 1);
+    }
+}
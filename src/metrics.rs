@@ -21,6 +21,11 @@ pub struct GasPoolMetrics {
     pub target_gas_budget_per_request: Histogram,
     pub reserve_duration_per_request: Histogram,
 
+    // Statistics about dry-run based gas budget auto-estimation
+    pub estimated_gas_budget_per_request: Histogram,
+    pub actual_gas_used_per_request: Histogram,
+    pub num_reservations_rejected_over_ceiling: IntCounter,
+
     // Statistics about the gas reservation response
     pub reserved_gas_coin_count_per_request: Histogram,
 
@@ -45,6 +50,11 @@ pub struct GasPoolMetrics {
     pub num_gas_coins_smashed: IntCounter,
 
     pub num_gas_pool_invariant_violations: IntCounter,
+
+    // === Reservation Admission Queue Metrics ===
+    pub reservation_queue_depth: IntGauge,
+    pub num_reservation_queue_evictions: IntCounterVec,
+    pub cur_num_penalized_clients: IntGauge,
 }
 
 impl GasPoolMetrics {
@@ -78,6 +88,22 @@ impl GasPoolMetrics {
                 "Reserve duration value in the reserve_gas RPC request",
                 registry,
             ),
+            estimated_gas_budget_per_request: Histogram::new_in_registry(
+                "estimated_gas_budget_per_request",
+                "Gas budget computed by dry-run auto-estimation in the reserve_gas RPC request",
+                registry,
+            ),
+            actual_gas_used_per_request: Histogram::new_in_registry(
+                "actual_gas_used_per_request",
+                "Actual gas used reported by execute_tx, for tuning the estimation safety multiplier",
+                registry,
+            ),
+            num_reservations_rejected_over_ceiling: register_int_counter_with_registry!(
+                "num_reservations_rejected_over_ceiling",
+                "Total number of reservations rejected because the estimated gas budget exceeded the configured ceiling",
+                registry,
+            )
+            .unwrap(),
             reserved_gas_coin_count_per_request: Histogram::new_in_registry(
                 "gas_coin_count_per_request",
                 "Number of gas coins reserved in the reserve_gas RPC response",
@@ -166,6 +192,25 @@ impl GasPoolMetrics {
                 registry,
             )
             .unwrap(),
+            reservation_queue_depth: register_int_gauge_with_registry!(
+                "reservation_queue_depth",
+                "Number of reserve_gas requests currently waiting in the admission queue",
+                registry,
+            )
+            .unwrap(),
+            num_reservation_queue_evictions: register_int_counter_vec_with_registry!(
+                "num_reservation_queue_evictions",
+                "Total number of queued reserve_gas requests evicted, keyed by client",
+                &["client"],
+                registry,
+            )
+            .unwrap(),
+            cur_num_penalized_clients: register_int_gauge_with_registry!(
+                "cur_num_penalized_clients",
+                "Current number of clients under penalization for letting reservations expire",
+                registry,
+            )
+            .unwrap(),
         })
     }
 
@@ -0,0 +1,555 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fair-queue admission control for `reserve_gas`.
+//!
+//! Modeled on a priority transaction pool: incoming reservation requests are
+//! scored (primary key = requested `target_gas_budget`, tie-broken by arrival
+//! order) and split into a *ready* set of admittable requests and a *future*
+//! set of requests blocked by their owner's per-client cap. As reservations
+//! are released, blocked clients are re-evaluated and their requests promoted
+//! from future to ready.
+//!
+//! Two caps bound a single client: a share cap (no client may hold more than a
+//! configured fraction of the total available coins) and a hard concurrency
+//! cap on outstanding reservations. Clients that reserve but let reservations
+//! expire without calling `execute_tx` are penalized: their score is demoted so
+//! they are evicted first when the pool is saturated. When the pool is full a
+//! new request evicts the lowest-scored ready request if it outranks it, or
+//! else the oldest future request if it outranks that; otherwise it is
+//! rejected. Future requests other than the oldest are not considered for
+//! eviction.
+
+use crate::metrics::GasPoolMetrics;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::sync::Arc;
+
+/// Opaque per-client identity. In practice this is the reservation auth token.
+pub type ClientId = String;
+
+/// A single pending reservation request awaiting admission.
+#[derive(Debug, Clone)]
+pub struct ReserveRequest {
+    pub client: ClientId,
+    pub target_gas_budget: u64,
+    /// Monotonic arrival sequence number, assigned by the queue.
+    pub seq: u64,
+}
+
+/// Ordering key for a queued request. Higher `target_gas_budget` is admitted
+/// first; ties are broken in favor of the earlier arrival (lower `seq`).
+/// Penalized requests carry a nonzero `penalty` which strictly lowers their
+/// score so they sort after all un-penalized requests and are evicted first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Score {
+    penalty: u32,
+    target_gas_budget: u64,
+    seq: u64,
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Lower penalty ranks higher; then higher budget; then earlier arrival.
+        other
+            .penalty
+            .cmp(&self.penalty)
+            .then(self.target_gas_budget.cmp(&other.target_gas_budget))
+            .then(other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Why a request could not be admitted immediately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdmitError {
+    /// The client is at its per-client share or concurrency cap; the request
+    /// was parked in the future set and will be retried on the next release.
+    Blocked,
+    /// The pool is saturated and this request scored no higher than either
+    /// the lowest ready request or the oldest future request, so it was
+    /// rejected outright.
+    Rejected,
+}
+
+#[derive(Debug, Default)]
+struct ClientState {
+    /// Number of outstanding (admitted, not yet released) reservations.
+    outstanding: u64,
+    /// Total gas budget currently held across outstanding reservations.
+    held_budget: u64,
+    /// Number of this client's requests sitting in the ready set, admitted
+    /// but not yet popped. Counted against the caps so a burst of submissions
+    /// cannot all pass the check before any of them is popped.
+    pending: u64,
+    /// Total gas budget of this client's requests sitting in the ready set.
+    pending_budget: u64,
+    /// Penalty accrued from reservations that expired without execution.
+    penalty: u32,
+}
+
+/// Configuration for the admission queue.
+#[derive(Debug, Clone)]
+pub struct AdmissionConfig {
+    /// Maximum fraction of total available coins a single client may hold,
+    /// expressed in basis points (e.g. 100 = 1%).
+    pub per_client_share_bps: u64,
+    /// Hard cap on the number of outstanding reservations per client.
+    pub per_client_concurrency: u64,
+    /// Maximum number of requests that may sit in the ready + future sets.
+    pub capacity: usize,
+    /// Penalty added each time one of a client's reservations expires.
+    pub expiry_penalty: u32,
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            per_client_share_bps: 100,
+            per_client_concurrency: 16,
+            capacity: 1024,
+            expiry_penalty: 1,
+        }
+    }
+}
+
+/// Fair-queue admission controller for reservations. Not internally
+/// synchronized; callers hold it behind the same lock that guards the pool.
+pub struct ReservationQueue {
+    config: AdmissionConfig,
+    metrics: Arc<GasPoolMetrics>,
+
+    /// Admittable requests, ordered by [`Score`]; the greatest is served first.
+    ready: BTreeSet<(Score, u64)>,
+    /// Requests blocked by their owner's per-client cap, in arrival order.
+    future: VecDeque<ReserveRequest>,
+    /// Backing storage for queued requests, keyed by `seq`.
+    requests: HashMap<u64, ReserveRequest>,
+
+    clients: HashMap<ClientId, ClientState>,
+    /// Total coins available in the pool, used to evaluate the share cap.
+    total_available_coins: u64,
+    next_seq: u64,
+}
+
+impl ReservationQueue {
+    pub fn new(
+        config: AdmissionConfig,
+        total_available_coins: u64,
+        metrics: Arc<GasPoolMetrics>,
+    ) -> Self {
+        Self {
+            config,
+            metrics,
+            ready: BTreeSet::new(),
+            future: VecDeque::new(),
+            requests: HashMap::new(),
+            clients: HashMap::new(),
+            total_available_coins,
+            next_seq: 0,
+        }
+    }
+
+    /// Update the view of total available coins, e.g. after the storage pool
+    /// reports a new balance. Changes the effective per-client share cap.
+    pub fn set_total_available_coins(&mut self, total: u64) {
+        self.total_available_coins = total;
+    }
+
+    fn per_client_cap(&self) -> u64 {
+        self.total_available_coins
+            .saturating_mul(self.config.per_client_share_bps)
+            / 10_000
+    }
+
+    fn score_for(&self, req: &ReserveRequest) -> Score {
+        Score {
+            penalty: self
+                .clients
+                .get(&req.client)
+                .map(|c| c.penalty)
+                .unwrap_or(0),
+            target_gas_budget: req.target_gas_budget,
+            seq: req.seq,
+        }
+    }
+
+    /// Whether admitting `req` would keep its client within both caps, taking
+    /// into account reservations already admitted to the ready set but not yet
+    /// popped (`pending`), not just outstanding ones.
+    fn within_caps(&self, req: &ReserveRequest) -> bool {
+        let state = self.clients.get(&req.client);
+        let concurrency = state.map(|c| c.outstanding + c.pending).unwrap_or(0);
+        let held = state
+            .map(|c| c.held_budget.saturating_add(c.pending_budget))
+            .unwrap_or(0);
+        concurrency < self.config.per_client_concurrency
+            && held.saturating_add(req.target_gas_budget) <= self.per_client_cap()
+    }
+
+    /// Insert a request into the ready set, charging it to its client's
+    /// pending counters so it counts against the caps while it waits.
+    fn insert_ready(&mut self, req: ReserveRequest) {
+        let score = self.score_for(&req);
+        let state = self.clients.entry(req.client.clone()).or_default();
+        state.pending += 1;
+        state.pending_budget = state.pending_budget.saturating_add(req.target_gas_budget);
+        self.ready.insert((score, req.seq));
+        self.requests.insert(req.seq, req);
+    }
+
+    /// Remove a request from the ready set, discharging its client's pending
+    /// counters. Returns the removed request, if any.
+    fn remove_ready(&mut self, score: Score, seq: u64) -> Option<ReserveRequest> {
+        self.ready.remove(&(score, seq));
+        let req = self.requests.remove(&seq)?;
+        if let Some(state) = self.clients.get_mut(&req.client) {
+            state.pending = state.pending.saturating_sub(1);
+            state.pending_budget = state.pending_budget.saturating_sub(req.target_gas_budget);
+        }
+        Some(req)
+    }
+
+    /// Submit a reservation request for admission. Returns `Ok` with the
+    /// assigned `seq` if the request was placed in the ready set, or an
+    /// [`AdmitError`] describing why it was parked or rejected.
+    pub fn submit(&mut self, client: ClientId, target_gas_budget: u64) -> Result<u64, AdmitError> {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let req = ReserveRequest {
+            client,
+            target_gas_budget,
+            seq,
+        };
+
+        if self.len() >= self.config.capacity {
+            // Pool is saturated: make room by evicting the lowest-scored queued
+            // request, but only if the newcomer outranks it.
+            if !self.try_evict_for(&req) {
+                // Nothing was evicted and the request never entered the queue,
+                // so this is a rejection, not an eviction.
+                return Err(AdmitError::Rejected);
+            }
+        }
+
+        let result = if self.within_caps(&req) {
+            self.insert_ready(req);
+            Ok(seq)
+        } else {
+            self.requests.insert(seq, req.clone());
+            self.future.push_back(req);
+            Err(AdmitError::Blocked)
+        };
+        self.sync_depth();
+        result
+    }
+
+    /// Pop the highest-scored admittable request, recording it as an
+    /// outstanding reservation for its client.
+    pub fn pop_ready(&mut self) -> Option<ReserveRequest> {
+        loop {
+            let &(score, seq) = self.ready.iter().next_back()?;
+            let req = self.remove_ready(score, seq)?;
+            // Re-validate against the client's already-admitted load: if the
+            // pool shrank (or several of the client's requests were popped in
+            // quick succession) this request no longer fits, so re-park it to
+            // the future set rather than admitting over the cap.
+            let state = self.clients.get(&req.client);
+            let outstanding = state.map(|c| c.outstanding).unwrap_or(0);
+            let held = state.map(|c| c.held_budget).unwrap_or(0);
+            if outstanding >= self.config.per_client_concurrency
+                || held.saturating_add(req.target_gas_budget) > self.per_client_cap()
+            {
+                self.requests.insert(req.seq, req.clone());
+                self.future.push_back(req);
+                self.sync_depth();
+                continue;
+            }
+            let state = self.clients.entry(req.client.clone()).or_default();
+            state.outstanding += 1;
+            state.held_budget = state.held_budget.saturating_add(req.target_gas_budget);
+            self.sync_depth();
+            return Some(req);
+        }
+    }
+
+    /// Record that a reservation was released through successful execution,
+    /// freeing its client's capacity, rehabilitating its penalty, and promoting
+    /// any now-admittable futures.
+    pub fn on_released(&mut self, client: &ClientId, target_gas_budget: u64) {
+        self.release_capacity(client, target_gas_budget);
+        self.rehabilitate(client);
+        self.promote_futures();
+        self.maybe_prune(client);
+    }
+
+    /// Decay a client's penalty on good behavior (a successful release). When
+    /// the penalty crosses back to zero the client is no longer counted in the
+    /// penalized-clients gauge.
+    fn rehabilitate(&mut self, client: &ClientId) {
+        if let Some(state) = self.clients.get_mut(client) {
+            if state.penalty > 0 {
+                state.penalty = state.penalty.saturating_sub(self.config.expiry_penalty);
+                if state.penalty == 0 {
+                    self.metrics.cur_num_penalized_clients.dec();
+                }
+            }
+        }
+    }
+
+    /// Drop a client's state once it holds no reservations, has none queued,
+    /// and carries no penalty, so the map does not leak one entry per distinct
+    /// auth token ever seen.
+    fn maybe_prune(&mut self, client: &ClientId) {
+        if let Some(state) = self.clients.get(client) {
+            if state.outstanding == 0
+                && state.pending == 0
+                && state.pending_budget == 0
+                && state.penalty == 0
+            {
+                self.clients.remove(client);
+            }
+        }
+    }
+
+    /// Record that a reservation expired without `execute_tx`, penalizing the
+    /// client before freeing its capacity.
+    pub fn on_expired(&mut self, client: &ClientId, target_gas_budget: u64) {
+        {
+            let state = self.clients.entry(client.clone()).or_default();
+            let was_penalized = state.penalty > 0;
+            state.penalty = state.penalty.saturating_add(self.config.expiry_penalty);
+            if !was_penalized {
+                self.metrics.cur_num_penalized_clients.inc();
+            }
+        }
+        self.release_capacity(client, target_gas_budget);
+        self.promote_futures();
+    }
+
+    fn release_capacity(&mut self, client: &ClientId, target_gas_budget: u64) {
+        if let Some(state) = self.clients.get_mut(client) {
+            state.outstanding = state.outstanding.saturating_sub(1);
+            state.held_budget = state.held_budget.saturating_sub(target_gas_budget);
+        }
+    }
+
+    /// Move every future request whose client is now within caps into the
+    /// ready set, preserving arrival order among the remaining blocked ones.
+    fn promote_futures(&mut self) {
+        let mut still_blocked = VecDeque::with_capacity(self.future.len());
+        while let Some(req) = self.future.pop_front() {
+            if self.within_caps(&req) {
+                self.insert_ready(req);
+            } else {
+                still_blocked.push_back(req);
+            }
+        }
+        self.future = still_blocked;
+        self.sync_depth();
+    }
+
+    /// Evict a single queued request to make room for `req`, returning whether
+    /// room was successfully made: the lowest-scored ready request if `req`
+    /// outranks it, otherwise the oldest future request if `req` outranks
+    /// that. Other future requests are not considered, so the evicted request
+    /// is not necessarily the lowest-scored one across both sets. A request is
+    /// only evicted if `req` scores strictly higher than it.
+    fn try_evict_for(&mut self, req: &ReserveRequest) -> bool {
+        let incoming = self.score_for(req);
+        // The lowest-scored candidate is the smallest ready entry, or failing
+        // that the oldest future entry (futures are unscored until promoted).
+        if let Some(&(lowest, seq)) = self.ready.iter().next() {
+            if incoming > lowest {
+                if let Some(victim) = self.remove_ready(lowest, seq) {
+                    self.evict_metric(&victim.client);
+                }
+                return true;
+            }
+        }
+        // The oldest future entry is the eviction candidate from that set, but
+        // only evict it if the newcomer actually outranks it.
+        if let Some(victim_req) = self.future.front() {
+            let victim_score = Score {
+                penalty: self
+                    .clients
+                    .get(&victim_req.client)
+                    .map(|c| c.penalty)
+                    .unwrap_or(0),
+                target_gas_budget: victim_req.target_gas_budget,
+                seq: victim_req.seq,
+            };
+            if incoming > victim_score {
+                let victim = self.future.pop_front().unwrap();
+                self.requests.remove(&victim.seq);
+                self.evict_metric(&victim.client);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn evict_metric(&self, client: &ClientId) {
+        self.metrics
+            .num_reservation_queue_evictions
+            .with_label_values(&[client])
+            .inc();
+    }
+
+    fn sync_depth(&self) {
+        self.metrics
+            .reservation_queue_depth
+            .set((self.ready.len() + self.future.len()) as i64);
+    }
+
+    /// Total number of requests currently queued across both sets.
+    pub fn len(&self) -> usize {
+        self.ready.len() + self.future.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(config: AdmissionConfig, total_available_coins: u64) -> ReservationQueue {
+        ReservationQueue::new(
+            config,
+            total_available_coins,
+            GasPoolMetrics::new_for_testing(),
+        )
+    }
+
+    fn evictions(q: &ReservationQueue, client: &str) -> u64 {
+        q.metrics
+            .num_reservation_queue_evictions
+            .with_label_values(&[client])
+            .get()
+    }
+
+    #[test]
+    fn concurrency_cap_parks_burst_to_future() {
+        let config = AdmissionConfig {
+            per_client_share_bps: 10_000,
+            per_client_concurrency: 2,
+            capacity: 1024,
+            expiry_penalty: 1,
+        };
+        let mut q = queue(config, 1_000_000);
+        // A single client submitting a burst: only `per_client_concurrency`
+        // requests are admitted, the rest are blocked into the future set.
+        assert!(matches!(q.submit("a".into(), 10), Ok(_)));
+        assert!(matches!(q.submit("a".into(), 10), Ok(_)));
+        assert_eq!(q.submit("a".into(), 10), Err(AdmitError::Blocked));
+        assert_eq!(q.ready.len(), 2);
+        assert_eq!(q.future.len(), 1);
+    }
+
+    #[test]
+    fn share_cap_parks_burst_to_future() {
+        let config = AdmissionConfig {
+            // 1% of 10_000 available coins == a 100-unit per-client cap.
+            per_client_share_bps: 100,
+            per_client_concurrency: 1_000,
+            capacity: 1024,
+            expiry_penalty: 1,
+        };
+        let mut q = queue(config, 10_000);
+        assert!(matches!(q.submit("a".into(), 60), Ok(_)));
+        // 60 + 60 exceeds the 100-unit share cap, so the second is blocked.
+        assert_eq!(q.submit("a".into(), 60), Err(AdmitError::Blocked));
+        assert_eq!(q.ready.len(), 1);
+        assert_eq!(q.future.len(), 1);
+    }
+
+    #[test]
+    fn release_promotes_future_to_ready() {
+        let config = AdmissionConfig {
+            per_client_share_bps: 10_000,
+            per_client_concurrency: 1,
+            capacity: 1024,
+            expiry_penalty: 1,
+        };
+        let mut q = queue(config, 1_000_000);
+        assert!(matches!(q.submit("a".into(), 10), Ok(_)));
+        let first = q.pop_ready().expect("first request is ready");
+        // Second submission is blocked by the concurrency cap while the first
+        // reservation is outstanding.
+        assert_eq!(q.submit("a".into(), 10), Err(AdmitError::Blocked));
+        assert_eq!(q.future.len(), 1);
+        // Releasing the first frees capacity and promotes the blocked request.
+        q.on_released(&first.client, first.target_gas_budget);
+        assert_eq!(q.future.len(), 0);
+        assert!(q.pop_ready().is_some());
+    }
+
+    #[test]
+    fn saturated_pool_evicts_lower_or_rejects() {
+        let config = AdmissionConfig {
+            per_client_share_bps: 10_000,
+            per_client_concurrency: 1_000,
+            capacity: 2,
+            expiry_penalty: 1,
+        };
+        let mut q = queue(config, 1_000_000);
+        assert!(matches!(q.submit("a".into(), 100), Ok(_)));
+        assert!(matches!(q.submit("b".into(), 200), Ok(_)));
+        // At capacity, a newcomer that outranks nobody is rejected outright and
+        // counted as neither an eviction against itself nor anyone else.
+        assert_eq!(q.submit("c".into(), 50), Err(AdmitError::Rejected));
+        assert_eq!(evictions(&q, "c"), 0);
+        assert_eq!(q.len(), 2);
+        // A higher-scored newcomer evicts the lowest-scored queued request.
+        assert!(matches!(q.submit("d".into(), 300), Ok(_)));
+        assert_eq!(evictions(&q, "a"), 1);
+        assert_eq!(q.len(), 2);
+    }
+
+    #[test]
+    fn penalty_demotes_and_evicts_first() {
+        let config = AdmissionConfig {
+            per_client_share_bps: 10_000,
+            per_client_concurrency: 1_000,
+            capacity: 2,
+            expiry_penalty: 1,
+        };
+        let mut q = queue(config, 1_000_000);
+        // Client "a" lets a reservation expire and is penalized.
+        q.on_expired(&"a".to_string(), 0);
+        assert_eq!(q.metrics.cur_num_penalized_clients.get(), 1);
+
+        assert!(matches!(q.submit("a".into(), 100), Ok(_)));
+        assert!(matches!(q.submit("b".into(), 100), Ok(_)));
+        // Equal budgets, but "a" is penalized, so it sorts lowest and is the
+        // one evicted when an un-penalized newcomer arrives at capacity.
+        assert!(matches!(q.submit("c".into(), 100), Ok(_)));
+        assert_eq!(evictions(&q, "a"), 1);
+    }
+
+    #[test]
+    fn penalty_decays_and_state_is_pruned_on_release() {
+        let config = AdmissionConfig {
+            per_client_share_bps: 10_000,
+            per_client_concurrency: 1_000,
+            capacity: 1024,
+            expiry_penalty: 1,
+        };
+        let mut q = queue(config, 1_000_000);
+        q.on_expired(&"a".to_string(), 0);
+        assert_eq!(q.metrics.cur_num_penalized_clients.get(), 1);
+        // A successful release rehabilitates the penalty back to zero and prunes
+        // the now-idle client so the map does not leak entries.
+        q.on_released(&"a".to_string(), 0);
+        assert_eq!(q.metrics.cur_num_penalized_clients.get(), 0);
+        assert!(!q.clients.contains_key("a"));
+    }
+}
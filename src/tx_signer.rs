@@ -4,6 +4,8 @@
 use reqwest::Client;
 use serde_json::json;
 use shared_crypto::intent::{Intent, IntentMessage};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{Signature, SuiKeyPair, ToFromBytes};
@@ -12,9 +14,25 @@ use sui_types::transaction::TransactionData;
 #[async_trait::async_trait]
 pub trait TxSigner: Send + Sync {
     async fn sign_transaction(&self, tx_data: &TransactionData) -> anyhow::Result<Signature>;
-    fn get_address(&self) -> SuiAddress;
+
+    /// The full set of sponsor addresses this signer is able to sign for.
+    /// Single-identity signers return a one-element set; multi-identity signers
+    /// such as [`RotatingTxSigner`] return every sponsor they manage.
+    fn get_addresses(&self) -> Vec<SuiAddress>;
+
+    /// Pick the sponsor address a new reservation should draw gas coins from.
+    /// Single-identity signers always return their only address; multi-identity
+    /// signers spread load across their sponsors so that no single address
+    /// becomes a coin-pool hot spot.
+    fn pick_sponsor(&self) -> SuiAddress {
+        self.get_addresses()
+            .into_iter()
+            .next()
+            .expect("a TxSigner must have at least one sponsor address")
+    }
+
     fn is_valid_address(&self, address: &SuiAddress) -> bool {
-        self.get_address() == *address
+        self.get_addresses().contains(address)
     }
 }
 
@@ -51,8 +69,136 @@ impl TxSigner for SidecarTxSigner {
         Ok(sig)
     }
 
-    fn get_address(&self) -> SuiAddress {
-        self.sponsor_address
+    fn get_addresses(&self) -> Vec<SuiAddress> {
+        vec![self.sponsor_address]
+    }
+}
+
+/// Signer that delegates the raw signing operation to an external KMS/HSM
+/// endpoint. The intent message bytes are posted to the configured KMS URL
+/// together with the key identifier, and the KMS returns the serialized
+/// [`Signature`]. The sponsor address (derived from the KMS-held public key)
+/// is supplied at construction time since the KMS does not echo it back.
+pub struct KmsTxSigner {
+    sponsor_address: SuiAddress,
+    kms_url: String,
+    key_id: String,
+    client: Client,
+}
+
+impl KmsTxSigner {
+    pub fn new(sponsor_address: SuiAddress, kms_url: String, key_id: String) -> Arc<Self> {
+        Arc::new(Self {
+            sponsor_address,
+            kms_url,
+            key_id,
+            client: Client::new(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl TxSigner for KmsTxSigner {
+    async fn sign_transaction(&self, tx_data: &TransactionData) -> anyhow::Result<Signature> {
+        let intent_msg = IntentMessage::new(Intent::sui_transaction(), tx_data);
+        let bytes = bcs::to_bytes(&intent_msg)?;
+        let resp = self
+            .client
+            .post(self.kms_url.clone())
+            .header("Content-Type", "application/json")
+            .json(&json!({"keyId": self.key_id, "txBytes": bytes}))
+            .send()
+            .await?;
+        let sig_bytes = resp.json::<Vec<u8>>().await?;
+        let sig = Signature::from_bytes(&sig_bytes)?;
+        Ok(sig)
+    }
+
+    fn get_addresses(&self) -> Vec<SuiAddress> {
+        vec![self.sponsor_address]
+    }
+}
+
+/// Reports the current reservation load (e.g. number of reserved gas coins) for
+/// a given sponsor address, as observed by the storage pool. Used by
+/// [`RotatingTxSigner`] to route new reservations to the least-loaded sponsor.
+pub type ReservationLoadFn = Arc<dyn Fn(&SuiAddress) -> u64 + Send + Sync>;
+
+/// Signer that fronts a set of sponsor identities and rotates reservations
+/// across them so that a single sponsor address does not become a coin-pool
+/// hot spot. By default sponsors are chosen round-robin; when a load reporter
+/// is supplied the sponsor with the lowest current reservation load is picked
+/// instead. Signing is delegated to the sub-signer that owns the transaction's
+/// gas.
+pub struct RotatingTxSigner {
+    signers: HashMap<SuiAddress, Arc<dyn TxSigner>>,
+    addresses: Vec<SuiAddress>,
+    next: AtomicUsize,
+    load: Option<ReservationLoadFn>,
+}
+
+impl RotatingTxSigner {
+    pub fn new(signers: Vec<Arc<dyn TxSigner>>) -> Arc<Self> {
+        Self::new_inner(signers, None)
+    }
+
+    /// Construct a rotating signer that routes each new reservation to the
+    /// sponsor currently reporting the lowest reservation load.
+    pub fn new_with_load_fn(
+        signers: Vec<Arc<dyn TxSigner>>,
+        load: ReservationLoadFn,
+    ) -> Arc<Self> {
+        Self::new_inner(signers, Some(load))
+    }
+
+    fn new_inner(signers: Vec<Arc<dyn TxSigner>>, load: Option<ReservationLoadFn>) -> Arc<Self> {
+        assert!(
+            !signers.is_empty(),
+            "RotatingTxSigner requires at least one sponsor signer"
+        );
+        let mut by_address = HashMap::new();
+        let mut addresses = Vec::new();
+        for signer in signers {
+            for address in signer.get_addresses() {
+                addresses.push(address);
+                by_address.insert(address, signer.clone());
+            }
+        }
+        Arc::new(Self {
+            signers: by_address,
+            addresses,
+            next: AtomicUsize::new(0),
+            load,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl TxSigner for RotatingTxSigner {
+    async fn sign_transaction(&self, tx_data: &TransactionData) -> anyhow::Result<Signature> {
+        let sponsor = tx_data.gas_data().owner;
+        let signer = self.signers.get(&sponsor).ok_or_else(|| {
+            anyhow::anyhow!("No sponsor signer registered for gas owner {sponsor}")
+        })?;
+        signer.sign_transaction(tx_data).await
+    }
+
+    fn get_addresses(&self) -> Vec<SuiAddress> {
+        self.addresses.clone()
+    }
+
+    fn pick_sponsor(&self) -> SuiAddress {
+        match &self.load {
+            Some(load) => *self
+                .addresses
+                .iter()
+                .min_by_key(|address| load(address))
+                .expect("RotatingTxSigner always has at least one sponsor"),
+            None => {
+                let idx = self.next.fetch_add(1, Ordering::Relaxed) % self.addresses.len();
+                self.addresses[idx]
+            }
+        }
     }
 }
 
@@ -74,7 +220,91 @@ impl TxSigner for TestTxSigner {
         Ok(sponsor_sig)
     }
 
-    fn get_address(&self) -> SuiAddress {
-        (&self.keypair.public()).into()
+    fn get_addresses(&self) -> Vec<SuiAddress> {
+        vec![(&self.keypair.public()).into()]
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::{ObjectID, SequenceNumber};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::digests::ObjectDigest;
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::transaction::{TransactionData, TransactionKind};
+
+    fn test_signer() -> Arc<TestTxSigner> {
+        let (_, kp): (_, AccountKeyPair) = get_key_pair();
+        TestTxSigner::new(SuiKeyPair::Ed25519(kp))
+    }
+
+    fn sponsored_tx(sender: SuiAddress, sponsor: SuiAddress) -> TransactionData {
+        let pt = ProgrammableTransactionBuilder::new().finish();
+        let gas_ref = (
+            ObjectID::ZERO,
+            SequenceNumber::from(0u64),
+            ObjectDigest::new([0u8; 32]),
+        );
+        TransactionData::new_with_gas_coins_allow_sponsor(
+            TransactionKind::ProgrammableTransaction(pt),
+            sender,
+            vec![gas_ref],
+            1_000_000,
+            1_000,
+            sponsor,
+        )
+    }
+
+    #[test]
+    fn pick_sponsor_round_robins() {
+        let s1 = test_signer();
+        let s2 = test_signer();
+        let a1 = s1.get_addresses()[0];
+        let a2 = s2.get_addresses()[0];
+        let rotating = RotatingTxSigner::new(vec![s1, s2]);
+        let picks: Vec<_> = (0..4).map(|_| rotating.pick_sponsor()).collect();
+        assert_eq!(picks, vec![a1, a2, a1, a2]);
+    }
+
+    #[test]
+    fn pick_sponsor_uses_lowest_load() {
+        let s1 = test_signer();
+        let s2 = test_signer();
+        let a1 = s1.get_addresses()[0];
+        let a2 = s2.get_addresses()[0];
+        let busy = a1;
+        let load: ReservationLoadFn =
+            Arc::new(move |address: &SuiAddress| if *address == busy { 10 } else { 1 });
+        let rotating = RotatingTxSigner::new_with_load_fn(vec![s1, s2], load);
+        assert_eq!(rotating.pick_sponsor(), a2);
+    }
+
+    #[test]
+    fn is_valid_address_matches_any_sponsor() {
+        let s1 = test_signer();
+        let s2 = test_signer();
+        let a1 = s1.get_addresses()[0];
+        let a2 = s2.get_addresses()[0];
+        let other = test_signer().get_addresses()[0];
+        let rotating = RotatingTxSigner::new(vec![s1, s2]);
+        assert!(rotating.is_valid_address(&a1));
+        assert!(rotating.is_valid_address(&a2));
+        assert!(!rotating.is_valid_address(&other));
+    }
+
+    #[tokio::test]
+    async fn sign_transaction_routes_by_gas_owner() {
+        let s1 = test_signer();
+        let s2 = test_signer();
+        let a1 = s1.get_addresses()[0];
+        let rotating = RotatingTxSigner::new(vec![s1, s2]);
+        // Gas owned by a known sponsor routes to its sub-signer and signs.
+        let tx = sponsored_tx(a1, a1);
+        assert!(rotating.sign_transaction(&tx).await.is_ok());
+        // Gas owned by an unregistered address hits the ok_or_else error path.
+        let stranger = test_signer().get_addresses()[0];
+        let tx = sponsored_tx(stranger, stranger);
+        assert!(rotating.sign_transaction(&tx).await.is_err());
+    }
+}